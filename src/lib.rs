@@ -1,5 +1,5 @@
 use solana_account_info::{next_account_info, AccountInfo};
-use solana_cpi::{invoke, invoke_signed};
+use solana_cpi::{invoke, invoke_signed, set_return_data};
 use solana_instruction::{AccountMeta, Instruction};
 use solana_msg::msg;
 use solana_program_entrypoint::{entrypoint, ProgramResult};
@@ -8,14 +8,97 @@ use solana_pubkey::Pubkey;
 entrypoint!(process_instruction);
 
 const SEED: &[u8] = b"fib";
-const DATA_LEN: u64 = 25; // a(8) + b(8) + n(8) + bump(1)
 const SYSTEM_PROGRAM: Pubkey = Pubkey::new_from_array([0u8; 32]);
 
+const MODE_FIXED: u8 = 0;
+const MODE_BIG: u8 = 1;
+
+// Fixed-width (u64) account layout: mode(1) + bump(1) + n(8) + a(8) + b(8)
+const DATA_LEN_FIXED: u64 = 26;
+
+// Bigint account layout header: mode(1) + bump(1) + n(8) + a_len(4) + b_len(4),
+// followed by `a_len` bytes of `a` (LE) and `b_len` bytes of `b` (LE).
+const BIG_HEADER_LEN: usize = 18;
+
+/// Per-instruction cap on how much an account's data can grow via `resize`
+/// (mirrors the runtime's `MAX_PERMITTED_DATA_INCREASE`).
+const MAX_PERMITTED_DATA_INCREASE: usize = 10 * 1024;
+
+/// Default number of fibonacci advances performed per self-CPI. Each recursion
+/// level can therefore clear `STEP_BATCH` steps instead of just one, so
+/// `ceil(n / STEP_BATCH)` recursion levels are needed instead of `n`.
+const STEP_BATCH: u64 = 64;
+
 /// Rent-exempt minimum: (128 overhead + data_len) * 3480 lamports/byte-year * 2 years
 const fn rent_minimum(data_len: u64) -> u64 {
     (128 + data_len) * 3_480 * 2
 }
 
+/// Explicit instruction discriminator. Byte 0 selects the variant; the rest
+/// of `instruction_data` is variant-specific payload. Replaces the old
+/// implicit "empty PDA data = init, otherwise step" dispatch.
+enum Instr {
+    /// Create the PDA and start computing fib(n), advancing up to `batch`
+    /// steps per self-CPI (defaults to [`STEP_BATCH`] when omitted). `big`
+    /// selects the arbitrary-precision layout over the fixed u64 one.
+    Init { n: u64, batch: u64, big: bool },
+    /// Advance an existing PDA by up to `batch` steps. This is what
+    /// `self_cpi` sends to recurse; which layout to advance is read back off
+    /// the PDA's own mode byte, not carried in the instruction.
+    Step { batch: u64 },
+    /// Restart an existing PDA from fib(0)=0, fib(1)=1 without re-creating
+    /// it, then resume computing fib(n) the same way `Init` does, advancing
+    /// up to `batch` steps per self-CPI (defaults to [`STEP_BATCH`] when
+    /// omitted).
+    Reset { n: u64, batch: u64 },
+    /// Transfer the PDA's lamports back to `payer` and reclaim the account.
+    Close,
+}
+
+impl Instr {
+    fn decode(data: &[u8]) -> Self {
+        assert!(!data.is_empty(), "empty instruction data");
+        match data[0] {
+            0 => {
+                assert!(data.len() >= 10, "Init needs a u64 n and a big-mode flag byte");
+                let n = u64::from_le_bytes(data[1..9].try_into().unwrap());
+                // `big` sits at a fixed offset (byte 9) ahead of the optional
+                // `batch`, so big-mode inits can still omit `batch` and fall
+                // back to STEP_BATCH instead of being forced to batch=1.
+                let big = data[9] != 0;
+                let batch = parse_batch(data, 10);
+                Instr::Init { n, batch, big }
+            }
+            1 => Instr::Step {
+                batch: parse_batch(data, 1),
+            },
+            2 => {
+                assert!(data.len() >= 9, "Reset needs a u64 n");
+                let n = u64::from_le_bytes(data[1..9].try_into().unwrap());
+                let batch = parse_batch(data, 9);
+                Instr::Reset { n, batch }
+            }
+            3 => Instr::Close,
+            other => panic!("unknown instruction discriminator {other}"),
+        }
+    }
+}
+
+/// Parse an optional trailing `batch: u64` out of instruction data starting at
+/// `offset`, falling back to the compiled-in [`STEP_BATCH`] when absent.
+/// A batch of zero would advance no steps per self-CPI and recurse with `n`
+/// unchanged, silently exhausting the CPI depth budget instead of making
+/// progress, so it's rejected here rather than left to fail opaquely.
+fn parse_batch(instruction_data: &[u8], offset: usize) -> u64 {
+    let batch = if instruction_data.len() >= offset + 8 {
+        u64::from_le_bytes(instruction_data[offset..offset + 8].try_into().unwrap())
+    } else {
+        STEP_BATCH
+    };
+    assert!(batch >= 1, "batch must be at least 1");
+    batch
+}
+
 /// Build a CreateAccount instruction for the system program (bincode).
 fn create_account_ix(
     from: &Pubkey,
@@ -36,6 +119,139 @@ fn create_account_ix(
     }
 }
 
+/// Build a Transfer instruction for the system program (bincode).
+fn transfer_ix(from: &Pubkey, to: &Pubkey, lamports: u64) -> Instruction {
+    let mut data = Vec::with_capacity(12);
+    data.extend_from_slice(&2u32.to_le_bytes());
+    data.extend_from_slice(&lamports.to_le_bytes());
+    Instruction {
+        program_id: SYSTEM_PROGRAM,
+        accounts: vec![AccountMeta::new(*from, true), AccountMeta::new(*to, false)],
+        data,
+    }
+}
+
+/// Report the final fibonacci value: log it and hand it back via return data
+/// so a caller can read the result without deserializing the PDA.
+fn finish(b: u64) {
+    msg!("done: {}", b);
+    set_return_data(&b.to_le_bytes());
+}
+
+/// Schoolbook little-endian addition. Walks both byte arrays summing with
+/// carry and appends a final carry byte if the result widens by one byte.
+fn add_le(a: &[u8], b: &[u8]) -> Vec<u8> {
+    let len = a.len().max(b.len());
+    let mut out = Vec::with_capacity(len + 1);
+    let mut carry = 0u16;
+    for i in 0..len {
+        let x = *a.get(i).unwrap_or(&0) as u16;
+        let y = *b.get(i).unwrap_or(&0) as u16;
+        let sum = x + y + carry;
+        out.push(sum as u8);
+        carry = sum >> 8;
+    }
+    if carry > 0 {
+        out.push(carry as u8);
+    }
+    out
+}
+
+/// Write the bigint layout's header and `a`/`b` bytes into the PDA, growing
+/// the account via `resize` first if the new values no longer fit, topping
+/// up lamports to the new rent-exempt minimum beforehand.
+fn write_big_state<'a>(
+    pda: &AccountInfo<'a>,
+    payer: &AccountInfo<'a>,
+    system: &AccountInfo<'a>,
+    bump: u8,
+    n: u64,
+    a: &[u8],
+    b: &[u8],
+) -> ProgramResult {
+    let needed = BIG_HEADER_LEN + a.len() + b.len();
+    let current = pda.try_borrow_data()?.len();
+
+    if needed > current {
+        let grow_by = needed - current;
+        assert!(
+            grow_by <= MAX_PERMITTED_DATA_INCREASE,
+            "resize of {grow_by} bytes exceeds MAX_PERMITTED_DATA_INCREASE"
+        );
+
+        let shortfall = rent_minimum(needed as u64).saturating_sub(pda.lamports());
+        if shortfall > 0 {
+            // payer is owned by the system program, not us, so we can't debit
+            // its lamports directly -- only the owning program may decrease
+            // an account's balance. Route the top-up through a real transfer.
+            invoke(
+                &transfer_ix(payer.key, pda.key, shortfall),
+                &[payer.clone(), pda.clone(), system.clone()],
+            )?;
+        }
+
+        pda.resize(needed)?; // zero-initializes the newly added bytes
+    }
+
+    let mut data = pda.try_borrow_mut_data()?;
+    assert!(data.len() >= needed, "account too small after resize");
+
+    data[0] = MODE_BIG;
+    data[1] = bump;
+    data[2..10].copy_from_slice(&n.to_le_bytes());
+    data[10..14].copy_from_slice(&(a.len() as u32).to_le_bytes());
+    data[14..18].copy_from_slice(&(b.len() as u32).to_le_bytes());
+    data[18..18 + a.len()].copy_from_slice(a);
+    data[18 + a.len()..18 + a.len() + b.len()].copy_from_slice(b);
+    Ok(())
+}
+
+/// Advance a bigint-mode PDA by up to `batch` steps, growing it via resize
+/// as the numbers widen, then recurse or report the final value.
+fn step_big<'a>(
+    program_id: &Pubkey,
+    pda: &AccountInfo<'a>,
+    payer: &AccountInfo<'a>,
+    system: &AccountInfo<'a>,
+    batch: u64,
+) -> ProgramResult {
+    let (bump, mut n, mut a, mut b) = {
+        let data = pda.try_borrow_data()?;
+        let bump = data[1];
+        let n = u64::from_le_bytes(data[2..10].try_into().unwrap());
+        let a_len = u32::from_le_bytes(data[10..14].try_into().unwrap()) as usize;
+        let b_len = u32::from_le_bytes(data[14..18].try_into().unwrap()) as usize;
+        let a = data[18..18 + a_len].to_vec();
+        let b = data[18 + a_len..18 + a_len + b_len].to_vec();
+        (bump, n, a, b)
+    };
+
+    let steps = batch.min(n);
+    for _ in 0..steps {
+        let new_b = add_le(&a, &b);
+        a = b;
+        b = new_b;
+        n -= 1;
+    }
+
+    write_big_state(pda, payer, system, bump, n, &a, &b)?;
+
+    msg!("step(big): n={} a_len={} b_len={}", n, a.len(), b.len());
+
+    if n > 0 {
+        self_cpi(program_id, pda, payer, system, batch, true)
+    } else if b.len() <= 8 {
+        let mut buf = [0u8; 8];
+        buf[..b.len()].copy_from_slice(&b);
+        finish(u64::from_le_bytes(buf));
+        Ok(())
+    } else {
+        msg!("done(big): {} bytes, see return data", b.len());
+        set_return_data(&b);
+        Ok(())
+    }
+}
+
 pub fn process_instruction(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
@@ -46,88 +262,235 @@ pub fn process_instruction(
     let payer = next_account_info(iter)?;
     let system = next_account_info(iter)?;
 
-    if pda.data_is_empty() {
-        // create PDA, store state, start recursion
-        assert!(instruction_data.len() >= 8, "need u64 n");
-        assert!(payer.is_signer, "payer must sign");
-        assert_eq!(*system.key, SYSTEM_PROGRAM, "bad system program");
-
-        let n = u64::from_le_bytes(instruction_data[0..8].try_into().unwrap());
-        let (expected, bump) =
-            Pubkey::find_program_address(&[SEED, payer.key.as_ref()], program_id);
-        assert_eq!(*pda.key, expected, "wrong PDA");
-
-        invoke_signed(
-            &create_account_ix(
-                payer.key,
-                pda.key,
-                rent_minimum(DATA_LEN),
-                DATA_LEN,
-                program_id,
-            ),
-            &[payer.clone(), pda.clone()],
-            &[&[SEED, payer.key.as_ref(), &[bump]]],
-        )?;
-
-        let mut data = pda.try_borrow_mut_data()?;
-        data[0..8].copy_from_slice(&0u64.to_le_bytes()); // a = fib(0)
-        data[8..16].copy_from_slice(&1u64.to_le_bytes()); // b = fib(1)
-        data[16..24].copy_from_slice(&n.to_le_bytes());
-        data[24] = bump;
-        drop(data);
-
-        msg!("init: a=0 b=1 n={}", n);
-
-        if n > 0 {
-            self_cpi(program_id, pda, payer, system)?;
+    match Instr::decode(instruction_data) {
+        Instr::Init { n, batch, big } => {
+            assert!(payer.is_signer, "payer must sign");
+            assert_eq!(*system.key, SYSTEM_PROGRAM, "bad system program");
+
+            let (expected, bump) =
+                Pubkey::find_program_address(&[SEED, payer.key.as_ref()], program_id);
+            assert_eq!(*pda.key, expected, "wrong PDA");
+
+            if big {
+                let space = (BIG_HEADER_LEN + 1 + 1) as u64; // a=[0], b=[1]
+                invoke_signed(
+                    &create_account_ix(payer.key, pda.key, rent_minimum(space), space, program_id),
+                    &[payer.clone(), pda.clone()],
+                    &[&[SEED, payer.key.as_ref(), &[bump]]],
+                )?;
+
+                write_big_state(pda, payer, system, bump, n, &[0], &[1])?;
+
+                msg!("init(big): a=0 b=1 n={} batch={}", n, batch);
+
+                if n > 0 {
+                    // big-mode steps may need to CPI a system transfer for
+                    // rent top-ups as the account grows, so keep payer a
+                    // genuine signer through the whole self-CPI chain.
+                    self_cpi(program_id, pda, payer, system, batch, true)?;
+                } else {
+                    finish(1);
+                }
+            } else {
+                invoke_signed(
+                    &create_account_ix(
+                        payer.key,
+                        pda.key,
+                        rent_minimum(DATA_LEN_FIXED),
+                        DATA_LEN_FIXED,
+                        program_id,
+                    ),
+                    &[payer.clone(), pda.clone()],
+                    &[&[SEED, payer.key.as_ref(), &[bump]]],
+                )?;
+
+                let mut data = pda.try_borrow_mut_data()?;
+                data[0] = MODE_FIXED;
+                data[1] = bump;
+                data[2..10].copy_from_slice(&n.to_le_bytes());
+                data[10..18].copy_from_slice(&0u64.to_le_bytes()); // a = fib(0)
+                data[18..26].copy_from_slice(&1u64.to_le_bytes()); // b = fib(1)
+                drop(data);
+
+                msg!("init: a=0 b=1 n={} batch={}", n, batch);
+
+                if n > 0 {
+                    // fixed-mode steps never touch lamports or the system
+                    // program, so payer is de-escalated through the chain.
+                    self_cpi(program_id, pda, payer, system, batch, false)?;
+                } else {
+                    finish(1);
+                }
+            }
         }
-    } else {
-        //  advance fibonacci, recurse if steps remain
-        let mut data = pda.try_borrow_mut_data()?;
-        let a = u64::from_le_bytes(data[0..8].try_into().unwrap());
-        let b = u64::from_le_bytes(data[8..16].try_into().unwrap());
-        let n = u64::from_le_bytes(data[16..24].try_into().unwrap());
-
-        if n == 0 {
-            msg!("done: {}", b);
-            return Ok(());
+        Instr::Step { batch } => {
+            assert!(pda.is_writable, "PDA must be writable");
+
+            let mode = pda.try_borrow_data()?[0];
+            if mode == MODE_BIG {
+                // big-mode steps may CPI a system transfer to top up rent as
+                // the account grows, so payer must remain a genuine writable
+                // signer here -- the transfer CPI debits payer's lamports,
+                // which the runtime rejects unless the account is writable;
+                // verify both bits rather than trusting a forged signer
+                // substituted for someone else's (non-writable) account.
+                assert!(
+                    payer.is_signer,
+                    "big-mode step needs a real payer signer for rent top-ups"
+                );
+                assert!(
+                    payer.is_writable,
+                    "big-mode step needs a writable payer for rent top-ups"
+                );
+                let (expected, _bump) =
+                    Pubkey::find_program_address(&[SEED, payer.key.as_ref()], program_id);
+                assert_eq!(*pda.key, expected, "wrong PDA for payer");
+
+                step_big(program_id, pda, payer, system, batch)?;
+            } else {
+                // Fixed-mode steps only ever mutate the PDA; they never touch
+                // the system program or move lamports, so payer must not be
+                // granted (or retain) any signer/writable privileges here.
+                assert!(!payer.is_signer, "step must not require payer signer");
+                assert!(!payer.is_writable, "step must not require payer writable");
+
+                // advance fibonacci by up to `batch` steps in a loop, recurse if steps remain
+                let mut data = pda.try_borrow_mut_data()?;
+                let mut a = u64::from_le_bytes(data[10..18].try_into().unwrap());
+                let mut b = u64::from_le_bytes(data[18..26].try_into().unwrap());
+                let mut n = u64::from_le_bytes(data[2..10].try_into().unwrap());
+
+                let steps = batch.min(n);
+                for _ in 0..steps {
+                    let new_b = a.checked_add(b).expect("overflow");
+                    a = b;
+                    b = new_b;
+                    n -= 1;
+                }
+
+                data[2..10].copy_from_slice(&n.to_le_bytes());
+                data[10..18].copy_from_slice(&a.to_le_bytes());
+                data[18..26].copy_from_slice(&b.to_le_bytes());
+                drop(data);
+
+                msg!("step: a={} b={} n={} batch={}", a, b, n, batch);
+
+                if n > 0 {
+                    self_cpi(program_id, pda, payer, system, batch, false)?;
+                } else {
+                    finish(b);
+                }
+            }
         }
+        Instr::Reset { n, batch } => {
+            // reuse the existing PDA, restarting from fib(0)=0, fib(1)=1 --
+            // same authorization Init/Close use, since the PDA address is
+            // public and anyone could otherwise wipe someone else's progress.
+            assert!(payer.is_signer, "payer must sign");
+            let (expected, _bump) =
+                Pubkey::find_program_address(&[SEED, payer.key.as_ref()], program_id);
+            assert_eq!(*pda.key, expected, "wrong PDA");
+            assert_eq!(*pda.owner, *program_id, "PDA not owned by this program");
+
+            let mode = pda.try_borrow_data()?[0];
+            if mode == MODE_BIG {
+                // big-mode steps may need to CPI a system transfer for rent
+                // top-ups as the account grows, so keep payer a genuine
+                // writable signer through the whole self-CPI chain.
+                assert!(
+                    payer.is_writable,
+                    "big-mode reset needs a writable payer for rent top-ups"
+                );
 
-        let new_b = a.checked_add(b).expect("overflow");
-        data[0..8].copy_from_slice(&b.to_le_bytes());
-        data[8..16].copy_from_slice(&new_b.to_le_bytes());
-        data[16..24].copy_from_slice(&(n - 1).to_le_bytes());
-        drop(data);
+                let bump = pda.try_borrow_data()?[1];
+                write_big_state(pda, payer, system, bump, n, &[0], &[1])?;
+                msg!("reset(big): a=0 b=1 n={} batch={}", n, batch);
 
-        msg!("step: a={} b={} n={}", b, new_b, n - 1);
+                if n > 0 {
+                    self_cpi(program_id, pda, payer, system, batch, true)?;
+                } else {
+                    finish(1);
+                }
+            } else {
+                let mut data = pda.try_borrow_mut_data()?;
+                data[2..10].copy_from_slice(&n.to_le_bytes());
+                data[10..18].copy_from_slice(&0u64.to_le_bytes());
+                data[18..26].copy_from_slice(&1u64.to_le_bytes());
+                drop(data);
+                msg!("reset: a=0 b=1 n={} batch={}", n, batch);
 
-        if n - 1 > 0 {
-            self_cpi(program_id, pda, payer, system)?;
-        } else {
-            msg!("done: {}", new_b);
+                if n > 0 {
+                    // fixed-mode steps never touch lamports or the system
+                    // program, so payer is de-escalated through the chain.
+                    self_cpi(program_id, pda, payer, system, batch, false)?;
+                } else {
+                    finish(1);
+                }
+            }
+        }
+        Instr::Close => {
+            // reclaim the PDA's rent back to payer
+            assert!(payer.is_signer, "payer must sign");
+            let (expected, _bump) =
+                Pubkey::find_program_address(&[SEED, payer.key.as_ref()], program_id);
+            assert_eq!(*pda.key, expected, "wrong PDA");
+
+            let payer_lamports = payer.lamports();
+            **payer.try_borrow_mut_lamports()? = payer_lamports
+                .checked_add(pda.lamports())
+                .expect("lamport overflow");
+            **pda.try_borrow_mut_lamports()? = 0;
+
+            pda.try_borrow_mut_data()?.fill(0);
+            pda.resize(0)?;
+            pda.assign(&SYSTEM_PROGRAM);
+
+            msg!("close: reclaimed rent to {}", payer.key);
         }
     }
 
     Ok(())
 }
 
-/// Recursive self-CPI: invoke this program again to advance one fibonacci step.
-/// Solana max invoke stack height is 5, so max 4 recursive steps after init.
+/// Recursive self-CPI: invoke this program again to advance up to `batch`
+/// fibonacci steps. Solana max invoke stack height is 5, so with `batch == 1`
+/// (one step per CPI) at most 4 recursive steps are possible after init;
+/// larger batches let `n` grow past that wall within the same depth budget.
+///
+/// Fixed-mode steps only mutate the PDA, so `payer` is de-escalated to
+/// non-writable/non-signer here -- a callee may drop privileges it was
+/// granted but must never escalate them, and there's nothing for fixed-mode
+/// step to do with payer's writable-signer status that init's CreateAccount
+/// needs. Big-mode steps, on the other hand, may need to CPI a system
+/// transfer for rent top-ups as the account grows, which requires a real
+/// signer; `payer_privileged` keeps payer a genuine signer through that
+/// chain instead, forwarding (never escalating) the signer bit init granted.
 fn self_cpi<'a>(
     program_id: &Pubkey,
     pda: &AccountInfo<'a>,
     payer: &AccountInfo<'a>,
     system: &AccountInfo<'a>,
+    batch: u64,
+    payer_privileged: bool,
 ) -> ProgramResult {
+    let mut data = vec![1u8]; // Step discriminator
+    data.extend_from_slice(&batch.to_le_bytes());
+
+    let payer_meta = if payer_privileged {
+        AccountMeta::new(*payer.key, true)
+    } else {
+        AccountMeta::new_readonly(*payer.key, false)
+    };
+
     invoke(
         &Instruction {
             program_id: *program_id,
             accounts: vec![
                 AccountMeta::new(*pda.key, false),
-                AccountMeta::new(*payer.key, true),
+                payer_meta,
                 AccountMeta::new_readonly(SYSTEM_PROGRAM, false),
             ],
-            data: vec![], // empty data = step mode
+            data,
         },
         &[pda.clone(), payer.clone(), system.clone()],
     )
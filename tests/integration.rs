@@ -20,7 +20,102 @@ fn program_test() -> (ProgramTest, Pubkey) {
     (pt, id)
 }
 
-fn init_ix(program_id: Pubkey, pda: Pubkey, payer: Pubkey, n: u64) -> Instruction {
+fn init_ix(
+    program_id: Pubkey,
+    pda: Pubkey,
+    payer: Pubkey,
+    n: u64,
+    batch: Option<u64>,
+) -> Instruction {
+    init_ix_inner(program_id, pda, payer, n, batch, false)
+}
+
+fn init_big_ix(program_id: Pubkey, pda: Pubkey, payer: Pubkey, n: u64) -> Instruction {
+    init_ix_inner(program_id, pda, payer, n, None, true)
+}
+
+fn init_ix_inner(
+    program_id: Pubkey,
+    pda: Pubkey,
+    payer: Pubkey,
+    n: u64,
+    batch: Option<u64>,
+    big: bool,
+) -> Instruction {
+    let mut data = vec![0u8]; // Init discriminator
+    data.extend_from_slice(&n.to_le_bytes());
+    // `big` sits at a fixed offset ahead of the optional `batch`, so big-mode
+    // inits can still omit `batch` and fall back to STEP_BATCH.
+    data.push(big as u8);
+    if let Some(batch) = batch {
+        data.extend_from_slice(&batch.to_le_bytes());
+    }
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(pda, false),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ],
+        data,
+    }
+}
+
+fn reset_ix(
+    program_id: Pubkey,
+    pda: Pubkey,
+    payer: Pubkey,
+    n: u64,
+    batch: Option<u64>,
+) -> Instruction {
+    let mut data = vec![2u8]; // Reset discriminator
+    data.extend_from_slice(&n.to_le_bytes());
+    if let Some(batch) = batch {
+        data.extend_from_slice(&batch.to_le_bytes());
+    }
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(pda, false),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ],
+        data,
+    }
+}
+
+/// Build a raw Step instruction, optionally forging `payer` as a signer
+/// and/or writable the way a malicious (or overly narrow) caller might try
+/// to -- all four (signer, writable) combinations are representable so
+/// tests can isolate either bit.
+fn step_ix(
+    program_id: Pubkey,
+    pda: Pubkey,
+    payer: Pubkey,
+    batch: u64,
+    forge_signer: bool,
+    forge_writable: bool,
+) -> Instruction {
+    let mut data = vec![1u8]; // Step discriminator
+    data.extend_from_slice(&batch.to_le_bytes());
+    let payer_meta = match (forge_signer, forge_writable) {
+        (true, true) => AccountMeta::new(payer, true),
+        (true, false) => AccountMeta::new_readonly(payer, true),
+        (false, true) => AccountMeta::new(payer, false),
+        (false, false) => AccountMeta::new_readonly(payer, false),
+    };
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(pda, false),
+            payer_meta,
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ],
+        data,
+    }
+}
+
+fn close_ix(program_id: Pubkey, pda: Pubkey, payer: Pubkey) -> Instruction {
     Instruction {
         program_id,
         accounts: vec![
@@ -28,18 +123,35 @@ fn init_ix(program_id: Pubkey, pda: Pubkey, payer: Pubkey, n: u64) -> Instructio
             AccountMeta::new(payer, true),
             AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
         ],
-        data: n.to_le_bytes().to_vec(),
+        data: vec![3u8], // Close discriminator
     }
 }
 
 fn read_state(data: &[u8]) -> (u64, u64, u64, u8) {
-    let a = u64::from_le_bytes(data[0..8].try_into().unwrap());
-    let b = u64::from_le_bytes(data[8..16].try_into().unwrap());
-    let n = u64::from_le_bytes(data[16..24].try_into().unwrap());
-    let bump = data[24];
+    let bump = data[1];
+    let n = u64::from_le_bytes(data[2..10].try_into().unwrap());
+    let a = u64::from_le_bytes(data[10..18].try_into().unwrap());
+    let b = u64::from_le_bytes(data[18..26].try_into().unwrap());
     (a, b, n, bump)
 }
 
+/// Read the bigint layout's `n` and little-endian `a`/`b` magnitudes.
+fn read_big_state(data: &[u8]) -> (u64, Vec<u8>, Vec<u8>) {
+    let n = u64::from_le_bytes(data[2..10].try_into().unwrap());
+    let a_len = u32::from_le_bytes(data[10..14].try_into().unwrap()) as usize;
+    let b_len = u32::from_le_bytes(data[14..18].try_into().unwrap()) as usize;
+    let a = data[18..18 + a_len].to_vec();
+    let b = data[18 + a_len..18 + a_len + b_len].to_vec();
+    (n, a, b)
+}
+
+/// Interpret a little-endian byte vector as a u128 for easy assertions.
+fn le_bytes_to_u128(bytes: &[u8]) -> u128 {
+    let mut buf = [0u8; 16];
+    buf[..bytes.len()].copy_from_slice(bytes);
+    u128::from_le_bytes(buf)
+}
+
 #[tokio::test]
 async fn init_n_zero() {
     let (pt, pid) = program_test();
@@ -48,7 +160,7 @@ async fn init_n_zero() {
     let (pda, bump) = Pubkey::find_program_address(&[SEED, payer.pubkey().as_ref()], &pid);
 
     let tx = Transaction::new_signed_with_payer(
-        &[init_ix(pid, pda, payer.pubkey(), 0)],
+        &[init_ix(pid, pda, payer.pubkey(), 0, Some(1))],
         Some(&payer.pubkey()),
         &[&payer],
         bh,
@@ -71,7 +183,7 @@ async fn fibonacci_n1() {
     let (pda, _) = Pubkey::find_program_address(&[SEED, payer.pubkey().as_ref()], &pid);
 
     let tx = Transaction::new_signed_with_payer(
-        &[init_ix(pid, pda, payer.pubkey(), 1)],
+        &[init_ix(pid, pda, payer.pubkey(), 1, Some(1))],
         Some(&payer.pubkey()),
         &[&payer],
         bh,
@@ -94,7 +206,7 @@ async fn fibonacci_n3() {
     let (pda, _) = Pubkey::find_program_address(&[SEED, payer.pubkey().as_ref()], &pid);
 
     let tx = Transaction::new_signed_with_payer(
-        &[init_ix(pid, pda, payer.pubkey(), 3)],
+        &[init_ix(pid, pda, payer.pubkey(), 3, Some(1))],
         Some(&payer.pubkey()),
         &[&payer],
         bh,
@@ -121,7 +233,7 @@ async fn fibonacci_n4_max_depth() {
     let (pda, _) = Pubkey::find_program_address(&[SEED, payer.pubkey().as_ref()], &pid);
 
     let tx = Transaction::new_signed_with_payer(
-        &[init_ix(pid, pda, payer.pubkey(), 4)],
+        &[init_ix(pid, pda, payer.pubkey(), 4, Some(1))],
         Some(&payer.pubkey()),
         &[&payer],
         bh,
@@ -148,7 +260,7 @@ async fn fibonacci_n5_exceeds_depth() {
     let (pda, _) = Pubkey::find_program_address(&[SEED, payer.pubkey().as_ref()], &pid);
 
     let tx = Transaction::new_signed_with_payer(
-        &[init_ix(pid, pda, payer.pubkey(), 5)],
+        &[init_ix(pid, pda, payer.pubkey(), 5, Some(1))],
         Some(&payer.pubkey()),
         &[&payer],
         bh,
@@ -157,6 +269,430 @@ async fn fibonacci_n5_exceeds_depth() {
     assert!(result.is_err(), "n=5 should exceed CPI depth limit");
 }
 
+#[tokio::test]
+async fn fibonacci_n5_with_batch_clears_depth_wall() {
+    // With the default batch (64 > n), init's single self-CPI runs all five
+    // steps in one Step loop, so n=5 no longer hits the stack-height-5 wall
+    // that batch=1 does.
+    let (pt, pid) = program_test();
+    let (banks, payer, bh) = pt.start().await;
+    let (pda, _) = Pubkey::find_program_address(&[SEED, payer.pubkey().as_ref()], &pid);
+
+    let tx = Transaction::new_signed_with_payer(
+        &[init_ix(pid, pda, payer.pubkey(), 5, None)],
+        Some(&payer.pubkey()),
+        &[&payer],
+        bh,
+    );
+    banks.process_transaction(tx).await.unwrap();
+
+    let acct = banks.get_account(pda).await.unwrap().unwrap();
+    let (a, b, n, _) = read_state(&acct.data);
+    assert_eq!(a, 5);
+    assert_eq!(b, 8);
+    assert_eq!(n, 0);
+}
+
+#[tokio::test]
+async fn fibonacci_batch_one_matches_batch_many() {
+    // A small batch that still needs several self-CPIs should reach the same
+    // result as the unbatched (batch=1) path, just in fewer recursion levels.
+    let (pt, pid) = program_test();
+    let (banks, payer, bh) = pt.start().await;
+    let (pda, _) = Pubkey::find_program_address(&[SEED, payer.pubkey().as_ref()], &pid);
+
+    let tx = Transaction::new_signed_with_payer(
+        &[init_ix(pid, pda, payer.pubkey(), 4, Some(2))],
+        Some(&payer.pubkey()),
+        &[&payer],
+        bh,
+    );
+    banks.process_transaction(tx).await.unwrap();
+
+    let acct = banks.get_account(pda).await.unwrap().unwrap();
+    let (a, b, n, _) = read_state(&acct.data);
+    assert_eq!(a, 3);
+    assert_eq!(b, 5);
+    assert_eq!(n, 0);
+}
+
+#[tokio::test]
+async fn fibonacci_n3_return_data_matches_fib() {
+    // The terminal CPI leg sets return data, so a caller (or this test) can
+    // read the final `b` (fib(n+1)) straight off the transaction without
+    // touching the PDA.
+    let (pt, pid) = program_test();
+    let (banks, payer, bh) = pt.start().await;
+    let (pda, _) = Pubkey::find_program_address(&[SEED, payer.pubkey().as_ref()], &pid);
+
+    let tx = Transaction::new_signed_with_payer(
+        &[init_ix(pid, pda, payer.pubkey(), 3, Some(1))],
+        Some(&payer.pubkey()),
+        &[&payer],
+        bh,
+    );
+    let result = banks.simulate_transaction(tx).await.unwrap();
+    let return_data = result
+        .simulation_details
+        .expect("simulation details")
+        .return_data
+        .expect("return data set");
+    assert_eq!(return_data.program_id, pid);
+    assert_eq!(
+        u64::from_le_bytes(return_data.data.try_into().unwrap()),
+        3 // fib(4)
+    );
+}
+
+#[tokio::test]
+async fn reset_restarts_existing_pda() {
+    let (pt, pid) = program_test();
+    let (banks, payer, bh) = pt.start().await;
+    let (pda, bump) = Pubkey::find_program_address(&[SEED, payer.pubkey().as_ref()], &pid);
+
+    let tx = Transaction::new_signed_with_payer(
+        &[init_ix(pid, pda, payer.pubkey(), 3, Some(1))],
+        Some(&payer.pubkey()),
+        &[&payer],
+        bh,
+    );
+    banks.process_transaction(tx).await.unwrap();
+
+    let bh = banks.get_latest_blockhash().await.unwrap();
+    let tx = Transaction::new_signed_with_payer(
+        &[reset_ix(pid, pda, payer.pubkey(), 1, Some(1))],
+        Some(&payer.pubkey()),
+        &[&payer],
+        bh,
+    );
+    banks.process_transaction(tx).await.unwrap();
+
+    // reset: a=0, b=1, n=1, then resumes computing the same way Init does --
+    // step: a=1, b=0+1=1, n=0
+    let acct = banks.get_account(pda).await.unwrap().unwrap();
+    let (a, b, n, stored_bump) = read_state(&acct.data);
+    assert_eq!(a, 1);
+    assert_eq!(b, 1);
+    assert_eq!(n, 0);
+    assert_eq!(stored_bump, bump);
+}
+
+#[tokio::test]
+async fn close_reclaims_rent_to_payer() {
+    let (pt, pid) = program_test();
+    let (banks, payer, bh) = pt.start().await;
+    let (pda, _) = Pubkey::find_program_address(&[SEED, payer.pubkey().as_ref()], &pid);
+
+    let tx = Transaction::new_signed_with_payer(
+        &[init_ix(pid, pda, payer.pubkey(), 0, Some(1))],
+        Some(&payer.pubkey()),
+        &[&payer],
+        bh,
+    );
+    banks.process_transaction(tx).await.unwrap();
+
+    let pda_lamports_before = banks.get_account(pda).await.unwrap().unwrap().lamports;
+    let payer_lamports_before = banks.get_balance(payer.pubkey()).await.unwrap();
+
+    let bh = banks.get_latest_blockhash().await.unwrap();
+    let tx = Transaction::new_signed_with_payer(
+        &[close_ix(pid, pda, payer.pubkey())],
+        Some(&payer.pubkey()),
+        &[&payer],
+        bh,
+    );
+    banks.process_transaction(tx).await.unwrap();
+
+    assert!(banks.get_account(pda).await.unwrap().is_none());
+    let payer_lamports_after = banks.get_balance(payer.pubkey()).await.unwrap();
+    // payer gains back the PDA's rent, net of a generous bound on the tx fee
+    let fee_margin = 10_000;
+    assert!(payer_lamports_after + fee_margin >= payer_lamports_before + pda_lamports_before);
+}
+
+#[tokio::test]
+async fn step_forging_payer_as_signer_rejected() {
+    let (pt, pid) = program_test();
+    let (banks, payer, bh) = pt.start().await;
+    let (pda, _) = Pubkey::find_program_address(&[SEED, payer.pubkey().as_ref()], &pid);
+
+    let tx = Transaction::new_signed_with_payer(
+        &[init_ix(pid, pda, payer.pubkey(), 2, Some(1))],
+        Some(&payer.pubkey()),
+        &[&payer],
+        bh,
+    );
+    banks.process_transaction(tx).await.unwrap();
+
+    // `payer` must not also be the tx fee payer here: CompiledKeys::compile
+    // forces the fee payer's account to writable+signer unconditionally,
+    // which would make this "forged" call indistinguishable from a
+    // legitimate one using the same key. A second, separately-funded
+    // keypair carries the fee so `payer`'s bits come solely from `step_ix`.
+    let fee_payer = solana_sdk::signature::Keypair::new();
+    let bh = banks.get_latest_blockhash().await.unwrap();
+    let tx = Transaction::new_signed_with_payer(
+        &[solana_sdk::system_instruction::transfer(
+            &payer.pubkey(),
+            &fee_payer.pubkey(),
+            1_000_000_000,
+        )],
+        Some(&payer.pubkey()),
+        &[&payer],
+        bh,
+    );
+    banks.process_transaction(tx).await.unwrap();
+
+    let bh = banks.get_latest_blockhash().await.unwrap();
+    let tx = Transaction::new_signed_with_payer(
+        &[step_ix(pid, pda, payer.pubkey(), 1, true, false)],
+        Some(&fee_payer.pubkey()),
+        &[&fee_payer, &payer],
+        bh,
+    );
+    let result = banks.process_transaction(tx).await;
+    assert!(result.is_err(), "step forging payer as signer should be rejected");
+}
+
+#[tokio::test]
+async fn step_forging_payer_as_writable_rejected() {
+    let (pt, pid) = program_test();
+    let (banks, payer, bh) = pt.start().await;
+    let (pda, _) = Pubkey::find_program_address(&[SEED, payer.pubkey().as_ref()], &pid);
+
+    let tx = Transaction::new_signed_with_payer(
+        &[init_ix(pid, pda, payer.pubkey(), 2, Some(1))],
+        Some(&payer.pubkey()),
+        &[&payer],
+        bh,
+    );
+    banks.process_transaction(tx).await.unwrap();
+
+    // As above, use a separately-funded fee payer so `payer`'s writable bit
+    // comes solely from `step_ix`'s own `AccountMeta` rather than from being
+    // forced writable+signer as the transaction's fee payer.
+    let fee_payer = solana_sdk::signature::Keypair::new();
+    let bh = banks.get_latest_blockhash().await.unwrap();
+    let tx = Transaction::new_signed_with_payer(
+        &[solana_sdk::system_instruction::transfer(
+            &payer.pubkey(),
+            &fee_payer.pubkey(),
+            1_000_000_000,
+        )],
+        Some(&payer.pubkey()),
+        &[&payer],
+        bh,
+    );
+    banks.process_transaction(tx).await.unwrap();
+
+    // A forged top-level Step marking payer writable-but-non-signer must be
+    // rejected too, not just a forged signer -- fixed-mode step has nothing
+    // to do with payer's lamports and must not be handed either privilege.
+    let bh = banks.get_latest_blockhash().await.unwrap();
+    let tx = Transaction::new_signed_with_payer(
+        &[step_ix(pid, pda, payer.pubkey(), 1, false, true)],
+        Some(&fee_payer.pubkey()),
+        &[&fee_payer],
+        bh,
+    );
+    let result = banks.process_transaction(tx).await;
+    assert!(result.is_err(), "step forging payer as writable should be rejected");
+}
+
+#[tokio::test]
+async fn step_big_forging_payer_non_writable_rejected() {
+    // Big-mode step's rent top-up CPI debits payer's lamports, so a real
+    // signer that's been stripped of its writable bit must still be
+    // rejected -- is_signer alone isn't enough.
+    let (pt, pid) = program_test();
+    let (banks, payer, bh) = pt.start().await;
+    let (pda, _) = Pubkey::find_program_address(&[SEED, payer.pubkey().as_ref()], &pid);
+
+    let tx = Transaction::new_signed_with_payer(
+        &[init_big_ix(pid, pda, payer.pubkey(), 2)],
+        Some(&payer.pubkey()),
+        &[&payer],
+        bh,
+    );
+    banks.process_transaction(tx).await.unwrap();
+
+    // As in the fixed-mode forgery tests, use a separately-funded fee payer
+    // so `payer`'s (signer, writable) bits come solely from `step_ix`'s own
+    // AccountMeta rather than from being forced writable+signer as the
+    // transaction's fee payer.
+    let fee_payer = solana_sdk::signature::Keypair::new();
+    let bh = banks.get_latest_blockhash().await.unwrap();
+    let tx = Transaction::new_signed_with_payer(
+        &[solana_sdk::system_instruction::transfer(
+            &payer.pubkey(),
+            &fee_payer.pubkey(),
+            1_000_000_000,
+        )],
+        Some(&payer.pubkey()),
+        &[&payer],
+        bh,
+    );
+    banks.process_transaction(tx).await.unwrap();
+
+    // `payer` really does sign this transaction (forge_signer=true), but the
+    // instruction's AccountMeta marks it read-only -- the program must see
+    // is_writable=false and reject it, not just check is_signer.
+    let bh = banks.get_latest_blockhash().await.unwrap();
+    let tx = Transaction::new_signed_with_payer(
+        &[step_ix(pid, pda, payer.pubkey(), 1, true, false)],
+        Some(&fee_payer.pubkey()),
+        &[&fee_payer, &payer],
+        bh,
+    );
+    let result = banks.process_transaction(tx).await;
+    assert!(
+        result.is_err(),
+        "big-mode step forging payer as non-writable should be rejected"
+    );
+}
+
+#[tokio::test]
+async fn step_big_forging_payer_non_signer_rejected() {
+    // Big-mode step's rent top-up CPI needs a genuine signer, not just a
+    // writable account -- a forged writable-but-non-signer payer must be
+    // rejected before the writable check is ever reached.
+    let (pt, pid) = program_test();
+    let (banks, payer, bh) = pt.start().await;
+    let (pda, _) = Pubkey::find_program_address(&[SEED, payer.pubkey().as_ref()], &pid);
+
+    let tx = Transaction::new_signed_with_payer(
+        &[init_big_ix(pid, pda, payer.pubkey(), 2)],
+        Some(&payer.pubkey()),
+        &[&payer],
+        bh,
+    );
+    banks.process_transaction(tx).await.unwrap();
+
+    // As above, use a separately-funded fee payer so `payer`'s writable bit
+    // comes solely from `step_ix`'s own AccountMeta rather than from being
+    // forced writable+signer as the transaction's fee payer.
+    let fee_payer = solana_sdk::signature::Keypair::new();
+    let bh = banks.get_latest_blockhash().await.unwrap();
+    let tx = Transaction::new_signed_with_payer(
+        &[solana_sdk::system_instruction::transfer(
+            &payer.pubkey(),
+            &fee_payer.pubkey(),
+            1_000_000_000,
+        )],
+        Some(&payer.pubkey()),
+        &[&payer],
+        bh,
+    );
+    banks.process_transaction(tx).await.unwrap();
+
+    let bh = banks.get_latest_blockhash().await.unwrap();
+    let tx = Transaction::new_signed_with_payer(
+        &[step_ix(pid, pda, payer.pubkey(), 1, false, true)],
+        Some(&fee_payer.pubkey()),
+        &[&fee_payer],
+        bh,
+    );
+    let result = banks.process_transaction(tx).await;
+    assert!(
+        result.is_err(),
+        "big-mode step forging payer as non-signer should be rejected"
+    );
+}
+
+#[tokio::test]
+async fn reset_wrong_payer_rejected() {
+    let (pt, pid) = program_test();
+    let (banks, payer, bh) = pt.start().await;
+    let (pda, _) = Pubkey::find_program_address(&[SEED, payer.pubkey().as_ref()], &pid);
+
+    let tx = Transaction::new_signed_with_payer(
+        &[init_ix(pid, pda, payer.pubkey(), 3, Some(1))],
+        Some(&payer.pubkey()),
+        &[&payer],
+        bh,
+    );
+    banks.process_transaction(tx).await.unwrap();
+
+    // An attacker who isn't the PDA's payer submits a Reset against it --
+    // even with a signature of their own, that signer doesn't derive this
+    // PDA, so the reset must be rejected rather than wiping the real payer's
+    // progress.
+    let attacker = solana_sdk::signature::Keypair::new();
+    let bh = banks.get_latest_blockhash().await.unwrap();
+    let tx = Transaction::new_signed_with_payer(
+        &[reset_ix(pid, pda, attacker.pubkey(), 1, Some(1))],
+        Some(&payer.pubkey()),
+        &[&payer, &attacker],
+        bh,
+    );
+    let result = banks.process_transaction(tx).await;
+    assert!(result.is_err(), "reset from a non-owning payer should be rejected");
+}
+
+#[tokio::test]
+async fn fibonacci_big_n10_matches_fixed() {
+    let (pt, pid) = program_test();
+    let (banks, payer, bh) = pt.start().await;
+    let (pda, _) = Pubkey::find_program_address(&[SEED, payer.pubkey().as_ref()], &pid);
+
+    let tx = Transaction::new_signed_with_payer(
+        &[init_big_ix(pid, pda, payer.pubkey(), 10)],
+        Some(&payer.pubkey()),
+        &[&payer],
+        bh,
+    );
+    banks.process_transaction(tx).await.unwrap();
+
+    let acct = banks.get_account(pda).await.unwrap().unwrap();
+    let (n, a, b) = read_big_state(&acct.data);
+    assert_eq!(n, 0);
+    assert_eq!(le_bytes_to_u128(&a), 55); // fib(10)
+    assert_eq!(le_bytes_to_u128(&b), 89); // fib(11)
+}
+
+#[tokio::test]
+async fn fibonacci_big_grows_past_u64() {
+    // fib(94) = 19740274219868223167, past u64::MAX (18446744073709551615) --
+    // well past the 64-bit ceiling the fixed mode hits -- exercising at least
+    // one resize (and the rent top-up CPI) as `b` widens beyond 8 bytes.
+    let (pt, pid) = program_test();
+    let (banks, payer, bh) = pt.start().await;
+    let (pda, _) = Pubkey::find_program_address(&[SEED, payer.pubkey().as_ref()], &pid);
+
+    let tx = Transaction::new_signed_with_payer(
+        &[init_big_ix(pid, pda, payer.pubkey(), 93)],
+        Some(&payer.pubkey()),
+        &[&payer],
+        bh,
+    );
+    banks.process_transaction(tx).await.unwrap();
+
+    let acct = banks.get_account(pda).await.unwrap().unwrap();
+    let (n, _a, b) = read_big_state(&acct.data);
+    assert_eq!(n, 0);
+    assert!(b.len() > 8, "fib(94) should have outgrown 8 bytes");
+    assert_eq!(le_bytes_to_u128(&b), 19_740_274_219_868_223_167);
+}
+
+#[tokio::test]
+async fn init_batch_zero_rejected() {
+    // batch=0 would advance zero steps per self-CPI and recurse with `n`
+    // unchanged, silently exhausting the CPI depth budget instead of making
+    // progress -- it must be rejected outright instead.
+    let (pt, pid) = program_test();
+    let (banks, payer, bh) = pt.start().await;
+    let (pda, _) = Pubkey::find_program_address(&[SEED, payer.pubkey().as_ref()], &pid);
+
+    let tx = Transaction::new_signed_with_payer(
+        &[init_ix(pid, pda, payer.pubkey(), 5, Some(0))],
+        Some(&payer.pubkey()),
+        &[&payer],
+        bh,
+    );
+    let result = banks.process_transaction(tx).await;
+    assert!(result.is_err(), "batch=0 should be rejected");
+}
+
 #[tokio::test]
 async fn wrong_pda_fails() {
     let (pt, pid) = program_test();
@@ -164,7 +700,7 @@ async fn wrong_pda_fails() {
 
     let wrong_pda = Pubkey::new_unique();
     let tx = Transaction::new_signed_with_payer(
-        &[init_ix(pid, wrong_pda, payer.pubkey(), 0)],
+        &[init_ix(pid, wrong_pda, payer.pubkey(), 0, Some(1))],
         Some(&payer.pubkey()),
         &[&payer],
         bh,